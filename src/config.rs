@@ -0,0 +1,168 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Light/dark theme choice, persisted alongside timer settings.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Theme {
+    Light,
+    Dark,
+}
+
+impl Theme {
+    fn parse(s: &str) -> Theme {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "dark" => Theme::Dark,
+            _ => Theme::Light,
+        }
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            Theme::Light => "light",
+            Theme::Dark => "dark",
+        }
+    }
+}
+
+/// Persisted settings loaded from (and written back to) `conf.ini`.
+pub struct Config {
+    pub presets: Vec<u32>,
+    pub default_duration: Duration,
+    pub theme: Theme,
+    /// Minute-of-hour wall-clock trigger points, e.g. `[30, 0]` fires at :30 and :00.
+    pub chimes: Vec<u32>,
+    pub sound_enabled: bool,
+    /// Alert gain, 0.0-1.0.
+    pub sound_volume: f32,
+    /// Path (relative to the executable) of the alert sound to decode via rodio.
+    pub sound_file: String,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            presets: vec![1, 5, 10, 25, 50],
+            default_duration: Duration::from_secs(25 * 60),
+            theme: Theme::Light,
+            chimes: Vec::new(),
+            sound_enabled: true,
+            sound_volume: 0.7,
+            sound_file: "assets/sound.ogg".to_string(),
+        }
+    }
+}
+
+/// Parses `mm:ss` into a `Duration`. Falls back to the given default on malformed input.
+fn parse_mmss(s: &str, default: Duration) -> Duration {
+    let mut parts = s.trim().splitn(2, ':');
+    let (Some(m), Some(sec)) = (parts.next(), parts.next()) else {
+        return default;
+    };
+    match (m.parse::<u64>(), sec.parse::<u64>()) {
+        (Ok(m), Ok(sec)) => Duration::from_secs(m * 60 + sec),
+        _ => default,
+    }
+}
+
+fn fmt_mmss(d: Duration) -> String {
+    let total = d.as_secs();
+    format!("{:02}:{:02}", total / 60, total % 60)
+}
+
+/// Parses a `chimes` value like `:30:,:00:` into minute-of-hour trigger points.
+fn parse_chimes(s: &str) -> Vec<u32> {
+    s.split(',')
+        .filter_map(|entry| entry.trim().trim_matches(':').parse::<u32>().ok())
+        .filter(|m| *m < 60)
+        .collect()
+}
+
+fn fmt_chimes(chimes: &[u32]) -> String {
+    chimes
+        .iter()
+        .map(|m| format!(":{:02}:", m))
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+impl Config {
+    /// Loads `conf.ini` next to the executable, creating it with defaults if missing.
+    pub fn load_or_create(path: &Path) -> io::Result<Config> {
+        if !path.exists() {
+            let config = Config::default();
+            config.save(path)?;
+            return Ok(config);
+        }
+
+        let text = fs::read_to_string(path)?;
+        let mut config = Config::default();
+        for line in text.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with(';') || line.starts_with('[') {
+                continue;
+            }
+            let Some((key, value)) = line.split_once('=') else {
+                continue;
+            };
+            match key.trim() {
+                "presets" => {
+                    let presets: Vec<u32> = value
+                        .split(',')
+                        .filter_map(|p| p.trim().parse().ok())
+                        .collect();
+                    if !presets.is_empty() {
+                        config.presets = presets;
+                    }
+                }
+                "default" => {
+                    config.default_duration = parse_mmss(value, config.default_duration);
+                }
+                "theme" => config.theme = Theme::parse(value),
+                "chimes" => config.chimes = parse_chimes(value),
+                "sound_enabled" => config.sound_enabled = value.trim() == "true",
+                "sound_volume" => {
+                    if let Ok(v) = value.trim().parse::<f32>() {
+                        config.sound_volume = v.clamp(0.0, 1.0);
+                    }
+                }
+                "sound_file" => config.sound_file = value.trim().to_string(),
+                _ => {}
+            }
+        }
+        Ok(config)
+    }
+
+    /// Writes the current settings back to `conf.ini`.
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        let presets = self
+            .presets
+            .iter()
+            .map(|p| p.to_string())
+            .collect::<Vec<_>>()
+            .join(",");
+        let mut body = format!(
+            "[Config]\npresets={}\ndefault={}\ntheme={}\n",
+            presets,
+            fmt_mmss(self.default_duration),
+            self.theme.as_str(),
+        );
+        if !self.chimes.is_empty() {
+            body.push_str(&format!("chimes={}\n", fmt_chimes(&self.chimes)));
+        }
+        body.push_str(&format!("sound_enabled={}\n", self.sound_enabled));
+        body.push_str(&format!("sound_volume={}\n", self.sound_volume));
+        body.push_str(&format!("sound_file={}\n", self.sound_file));
+        fs::write(path, body)
+    }
+}
+
+/// Returns the path to `conf.ini` next to the running executable.
+pub fn default_config_path() -> PathBuf {
+    std::env::current_exe()
+        .ok()
+        .and_then(|p| p.parent().map(|p| p.to_path_buf()))
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("conf.ini")
+}
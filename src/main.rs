@@ -1,7 +1,16 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
+mod config;
+mod sound;
+mod timer;
+
+use chrono::Timelike;
+use config::{Config, Theme};
 use eframe::egui;
+use sound::AlertPlayer;
+use std::path::PathBuf;
 use std::time::{Duration, Instant};
+use timer::{Direction, PlayState, TimerCore};
 
 fn fmt_hhmmss(total_secs: u64) -> String {
     let h = total_secs / 3600;
@@ -14,53 +23,241 @@ fn fmt_hhmmss(total_secs: u64) -> String {
     }
 }
 
+/// Which half of a Pomodoro work/rest pair is currently counting down.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum Phase {
+    Work,
+    Rest,
+}
+
+/// Digits buffered while the user keys in a new duration "microwave style":
+/// up to 4 digits, the last two are seconds and the rest are minutes.
+const MAX_DURATION_ENTRY_DIGITS: usize = 4;
+
+fn key_to_digit(key: egui::Key) -> Option<char> {
+    use egui::Key::*;
+    Some(match key {
+        Num0 => '0',
+        Num1 => '1',
+        Num2 => '2',
+        Num3 => '3',
+        Num4 => '4',
+        Num5 => '5',
+        Num6 => '6',
+        Num7 => '7',
+        Num8 => '8',
+        Num9 => '9',
+        _ => return None,
+    })
+}
+
+fn digit_to_key(d: u8) -> egui::Key {
+    use egui::Key::*;
+    match d {
+        0 => Num0,
+        1 => Num1,
+        2 => Num2,
+        3 => Num3,
+        4 => Num4,
+        5 => Num5,
+        6 => Num6,
+        7 => Num7,
+        8 => Num8,
+        _ => Num9,
+    }
+}
+
+/// Builds a synthetic key-press event, for the on-screen keypad to inject
+/// through the same `raw_input_hook` path real keystrokes take.
+fn synthetic_key_event(key: egui::Key) -> egui::Event {
+    egui::Event::Key {
+        key,
+        physical_key: None,
+        pressed: true,
+        repeat: false,
+        modifiers: egui::Modifiers::NONE,
+    }
+}
+
 struct AppState {
     // Set duration controls
     set_minutes: u32,
     set_seconds: u32,
 
     // Timer runtime state
-    running: bool,
-    remaining: Duration,
+    timer: TimerCore,
     last_tick: Option<Instant>,
 
     finished_modal: bool,
+
+    // Pomodoro state
+    phase: Phase,
+    work_duration: Duration,
+    rest_duration: Duration,
+    postpone_duration: Duration,
+    cycles_total: u32,
+    cycles_remaining: u32,
+
+    // Persisted settings
+    config: Config,
+    config_path: PathBuf,
+
+    // Chime state
+    chime_modal: bool,
+    last_chime_fired: Option<(u32, u32)>,
+
+    // Finish alert
+    alert_player: Option<AlertPlayer>,
+    active_alert: Option<rodio::Sink>,
+
+    // Keyboard/touch duration entry
+    duration_entry: String,
+    show_keypad: bool,
+    pending_synthetic_events: Vec<egui::Event>,
 }
 
 impl Default for AppState {
     fn default() -> Self {
-        let initial = Duration::from_secs(5 * 60);
+        let config_path = config::default_config_path();
+        let config = Config::load_or_create(&config_path).unwrap_or_default();
+        AppState::new(config, config_path)
+    }
+}
+
+impl AppState {
+    fn new(config: Config, config_path: PathBuf) -> Self {
+        let initial = config.default_duration;
+        let set_minutes = (initial.as_secs() / 60) as u32;
+        let set_seconds = (initial.as_secs() % 60) as u32;
+        let alert_player = AlertPlayer::new(config_path.with_file_name(&config.sound_file));
         Self {
-            set_minutes: 5,
-            set_seconds: 0,
-            running: false,
-            remaining: initial,
+            set_minutes,
+            set_seconds,
+            timer: TimerCore::new(Direction::CountDown, initial),
             last_tick: None,
             finished_modal: false,
+            phase: Phase::Work,
+            work_duration: initial,
+            rest_duration: Duration::from_secs(5 * 60),
+            postpone_duration: Duration::from_secs(5 * 60),
+            cycles_total: 1,
+            cycles_remaining: 1,
+            config,
+            config_path,
+            chime_modal: false,
+            last_chime_fired: None,
+            alert_player,
+            active_alert: None,
+            duration_entry: String::new(),
+            show_keypad: false,
+            pending_synthetic_events: Vec::new(),
         }
     }
-}
 
-impl AppState {
+    /// Plays the finish alert if sound is enabled, replacing any alert already playing.
+    fn play_alert(&mut self) {
+        if !self.config.sound_enabled {
+            return;
+        }
+        if let Some(player) = &self.alert_player {
+            self.active_alert = player.play(self.config.sound_volume);
+        }
+    }
+
+    /// Stops the currently playing alert, if any.
+    fn stop_alert(&mut self) {
+        if let Some(sink) = self.active_alert.take() {
+            sink.stop();
+        }
+    }
+
+    fn is_running(&self) -> bool {
+        self.timer.play_state() == PlayState::Running
+    }
+
+    fn push_duration_digit(&mut self, digit: char) {
+        if self.duration_entry.len() < MAX_DURATION_ENTRY_DIGITS {
+            self.duration_entry.push(digit);
+        }
+    }
+
+    /// Applies the buffered digits as `mm:ss` (last two digits are seconds)
+    /// and clears the buffer.
+    fn apply_duration_entry(&mut self) {
+        if self.duration_entry.is_empty() {
+            return;
+        }
+        let padded = format!("{:0>4}", self.duration_entry);
+        let (mins, secs) = padded.split_at(2);
+        self.set_minutes = mins.parse().unwrap_or(0);
+        self.set_seconds = secs.parse().unwrap_or(0);
+        self.apply_set_duration();
+        self.duration_entry.clear();
+    }
+
     fn apply_set_duration(&mut self) {
         let secs = (self.set_minutes as u64) * 60 + (self.set_seconds as u64);
-        self.remaining = Duration::from_secs(secs.max(1)); // avoid 0
+        let duration = Duration::from_secs(secs.max(1)); // avoid 0
+        self.work_duration = duration;
+        self.phase = Phase::Work;
+        self.cycles_remaining = self.cycles_total;
+        self.timer.set_duration(duration);
         self.finished_modal = false;
         self.last_tick = None;
+
+        self.config.default_duration = duration;
+        let _ = self.config.save(&self.config_path);
+    }
+
+    /// Adds `postpone_duration` back onto the current (just-finished) work phase and resumes.
+    fn postpone(&mut self) {
+        self.timer.set_duration(self.postpone_duration);
+        self.finished_modal = false;
+        self.stop_alert();
+        self.timer.play();
+        self.last_tick = Some(Instant::now());
+    }
+
+    /// Jumps straight from a finished work phase into the rest phase.
+    fn start_rest(&mut self) {
+        self.phase = Phase::Rest;
+        self.timer.set_duration(self.rest_duration);
+        self.finished_modal = false;
+        self.stop_alert();
+        self.timer.play();
+        self.last_tick = Some(Instant::now());
+    }
+
+    /// Switches between countdown and stopwatch mode, resetting the run.
+    fn toggle_mode(&mut self) {
+        let next = match self.timer.direction() {
+            Direction::CountDown => Direction::CountUp,
+            Direction::CountUp => Direction::CountDown,
+        };
+        self.timer.set_direction(next);
+        self.last_tick = None;
+        self.finished_modal = false;
     }
 
     fn reset(&mut self) {
-        self.running = false;
-        self.apply_set_duration();
+        match self.timer.direction() {
+            Direction::CountDown => self.apply_set_duration(),
+            Direction::CountUp => {
+                self.timer.reset();
+                self.last_tick = None;
+            }
+        }
     }
 
     fn toggle(&mut self) {
-        self.running = !self.running;
+        self.timer.toggle();
         self.last_tick = Some(Instant::now());
     }
 
     fn tick(&mut self) {
-        if !self.running {
+        self.check_chimes();
+
+        if !self.is_running() {
             self.last_tick = None;
             return;
         }
@@ -73,25 +270,111 @@ impl AppState {
 
         let dt = now.saturating_duration_since(prev);
         self.last_tick = Some(now);
+        self.timer.advance(dt);
 
-        if dt >= self.remaining {
-            self.remaining = Duration::from_secs(0);
-            self.running = false;
-            self.finished_modal = true;
-        } else {
-            self.remaining -= dt;
+        if self.timer.play_state() == PlayState::Finished {
+            self.advance_phase();
         }
     }
+
+    /// Called when the active phase's countdown reaches zero. A finished work
+    /// phase stops and waits for the user (Postpone / Start Rest / OK); a
+    /// finished rest phase auto-advances back into the next work phase,
+    /// decrementing the cycle counter after each work+rest pair.
+    fn advance_phase(&mut self) {
+        match self.phase {
+            Phase::Work => {
+                self.finished_modal = true;
+                self.play_alert();
+            }
+            Phase::Rest => {
+                self.cycles_remaining = self.cycles_remaining.saturating_sub(1);
+                if self.cycles_remaining == 0 {
+                    self.finished_modal = true;
+                    self.play_alert();
+                } else {
+                    self.phase = Phase::Work;
+                    self.timer.set_duration(self.work_duration);
+                    self.timer.play();
+                    self.last_tick = Some(Instant::now());
+                }
+            }
+        }
+    }
+
+    /// Checks the current wall-clock time against the configured chime points
+    /// (e.g. `:30` and `:00` of every hour) and raises the chime modal once per match.
+    fn check_chimes(&mut self) {
+        if self.config.chimes.is_empty() {
+            return;
+        }
+
+        let now = chrono::Local::now();
+        let (hour, minute) = (now.hour(), now.minute());
+        if !self.config.chimes.contains(&minute) {
+            return;
+        }
+
+        if self.last_chime_fired == Some((hour, minute)) {
+            return;
+        }
+
+        self.last_chime_fired = Some((hour, minute));
+        self.chime_modal = true;
+    }
 }
 
 impl eframe::App for AppState {
+    /// Intercepts input before egui processes it: builds up a typed duration
+    /// from digit keys (real or synthesized by the on-screen keypad) and
+    /// swallows Space/R while a widget has focus, so typing into a field
+    /// doesn't accidentally start/reset the timer.
+    fn raw_input_hook(&mut self, ctx: &egui::Context, raw_input: &mut egui::RawInput) {
+        raw_input.events.append(&mut self.pending_synthetic_events);
+
+        let field_focused = ctx.memory(|m| m.focused().is_some());
+        raw_input.events.retain(|event| {
+            !(field_focused
+                && matches!(
+                    event,
+                    egui::Event::Key {
+                        key: egui::Key::Space | egui::Key::R,
+                        ..
+                    }
+                ))
+        });
+
+        if field_focused {
+            return;
+        }
+
+        for event in &raw_input.events {
+            let egui::Event::Key {
+                key,
+                pressed: true,
+                repeat: false,
+                ..
+            } = event
+            else {
+                continue;
+            };
+            if let Some(digit) = key_to_digit(*key) {
+                self.push_duration_digit(digit);
+            } else if *key == egui::Key::Backspace {
+                self.duration_entry.pop();
+            } else if *key == egui::Key::Enter {
+                self.apply_duration_entry();
+            }
+        }
+    }
+
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
         // Keyboard shortcuts
         let space = ctx.input(|i| i.key_pressed(egui::Key::Space));
         let r = ctx.input(|i| i.key_pressed(egui::Key::R));
         let esc = ctx.input(|i| i.key_pressed(egui::Key::Escape));
 
-        if space {
+        if space && !self.finished_modal {
             self.toggle();
         }
         if r {
@@ -99,16 +382,24 @@ impl eframe::App for AppState {
         }
         if esc && self.finished_modal {
             self.finished_modal = false;
+            self.stop_alert();
         }
 
         // Advance timer
         self.tick();
 
         // If running, keep UI smooth
-        if self.running {
+        if self.is_running() {
             ctx.request_repaint_after(Duration::from_millis(16));
         }
 
+        // Keep polling the wall clock for chimes even while idle, otherwise an
+        // app sitting paused with no modal open would never repaint across a
+        // configured :30/:00 trigger.
+        if !self.config.chimes.is_empty() {
+            ctx.request_repaint_after(Duration::from_secs(1));
+        }
+
         // UI
         egui::CentralPanel::default().show(ctx, |ui| {
             ui.add_space(8.0);
@@ -123,7 +414,10 @@ impl eframe::App for AppState {
             ui.add_space(12.0);
 
             // Big time display
-            let secs = self.remaining.as_secs();
+            let secs = match self.timer.direction() {
+                Direction::CountDown => self.timer.remaining().as_secs(),
+                Direction::CountUp => self.timer.elapsed().as_secs(),
+            };
             let time_str = fmt_hhmmss(secs);
 
             ui.with_layout(egui::Layout::top_down(egui::Align::Center), |ui| {
@@ -157,15 +451,44 @@ impl eframe::App for AppState {
                 if ui.button("Apply").clicked() {
                     self.apply_set_duration();
                 }
+
+                ui.separator();
+
+                if ui
+                    .checkbox(&mut self.config.sound_enabled, "Sound")
+                    .changed()
+                {
+                    let _ = self.config.save(&self.config_path);
+                }
+                let volume_response = ui.add_enabled(
+                    self.config.sound_enabled,
+                    egui::DragValue::new(&mut self.config.sound_volume)
+                        .clamp_range(0.0..=1.0)
+                        .speed(0.01)
+                        .prefix("vol "),
+                );
+                if volume_response.drag_stopped() || volume_response.lost_focus() {
+                    let _ = self.config.save(&self.config_path);
+                }
             });
 
+            if !self.duration_entry.is_empty() {
+                ui.label(
+                    egui::RichText::new(format!(
+                        "Typing: {}  (Enter to apply, Backspace to edit)",
+                        self.duration_entry
+                    ))
+                    .weak(),
+                );
+            }
+
             ui.add_space(6.0);
 
             // Presets
             ui.horizontal(|ui| {
                 ui.label("Presets:");
-                for (label, mins) in [("1", 1), ("5", 5), ("10", 10), ("25", 25), ("50", 50)] {
-                    if ui.button(label).clicked() {
+                for mins in self.config.presets.clone() {
+                    if ui.button(mins.to_string()).clicked() {
                         self.set_minutes = mins;
                         self.set_seconds = 0;
                         self.apply_set_duration();
@@ -173,11 +496,54 @@ impl eframe::App for AppState {
                 }
             });
 
-            ui.add_space(14.0);
+            ui.add_space(6.0);
+
+            // Pomodoro controls
+            ui.horizontal(|ui| {
+                ui.label("Pomodoro:");
+                let mut rest_mins = (self.rest_duration.as_secs() / 60) as u32;
+                if ui
+                    .add(
+                        egui::DragValue::new(&mut rest_mins)
+                            .clamp_range(0..=999)
+                            .suffix(" rest min"),
+                    )
+                    .changed()
+                {
+                    self.rest_duration = Duration::from_secs((rest_mins as u64) * 60);
+                }
+                let mut postpone_mins = (self.postpone_duration.as_secs() / 60) as u32;
+                if ui
+                    .add(
+                        egui::DragValue::new(&mut postpone_mins)
+                            .clamp_range(0..=999)
+                            .suffix(" postpone min"),
+                    )
+                    .changed()
+                {
+                    self.postpone_duration = Duration::from_secs((postpone_mins as u64) * 60);
+                }
+                if ui
+                    .add(
+                        egui::DragValue::new(&mut self.cycles_total)
+                            .clamp_range(1..=99)
+                            .suffix(" cycles"),
+                    )
+                    .changed()
+                {
+                    self.cycles_remaining = self.cycles_total;
+                }
+                ui.label(format!(
+                    "[{:?}, {} left]",
+                    self.phase, self.cycles_remaining
+                ));
+            });
+
+            ui.add_space(8.0);
 
             // Controls
             ui.horizontal(|ui| {
-                let start_label = if self.running { "Pause" } else { "Start" };
+                let start_label = if self.is_running() { "Pause" } else { "Start" };
                 if ui.add_sized([110.0, 34.0], egui::Button::new(start_label)).clicked() {
                     self.toggle();
                 }
@@ -186,12 +552,82 @@ impl eframe::App for AppState {
                 }
                 if ui.add_sized([110.0, 34.0], egui::Button::new("Set = Remaining")).clicked() {
                     // convenient: copy current remaining into set controls
-                    let total = self.remaining.as_secs();
+                    let total = self.timer.remaining().as_secs();
                     self.set_minutes = (total / 60) as u32;
                     self.set_seconds = (total % 60) as u32;
                 }
+                let mode_label = match self.timer.direction() {
+                    Direction::CountDown => "Mode: Countdown",
+                    Direction::CountUp => "Mode: Stopwatch",
+                };
+                if ui.button(mode_label).clicked() {
+                    self.toggle_mode();
+                }
+                if ui
+                    .selectable_label(self.show_keypad, "Keypad")
+                    .clicked()
+                {
+                    self.show_keypad = !self.show_keypad;
+                }
             });
 
+            if self.show_keypad {
+                ui.add_space(8.0);
+                egui::Grid::new("virtual_keypad")
+                    .spacing([6.0, 6.0])
+                    .show(ui, |ui| {
+                        for row in [[1u8, 2, 3], [4, 5, 6], [7, 8, 9]] {
+                            for digit in row {
+                                if ui
+                                    .add_sized([44.0, 36.0], egui::Button::new(digit.to_string()))
+                                    .clicked()
+                                {
+                                    self.pending_synthetic_events
+                                        .push(synthetic_key_event(digit_to_key(digit)));
+                                }
+                            }
+                            ui.end_row();
+                        }
+                        if ui.add_sized([44.0, 36.0], egui::Button::new("0")).clicked() {
+                            self.pending_synthetic_events
+                                .push(synthetic_key_event(digit_to_key(0)));
+                        }
+                        if ui
+                            .add_sized([44.0, 36.0], egui::Button::new("\u{232b}"))
+                            .clicked()
+                        {
+                            self.pending_synthetic_events
+                                .push(synthetic_key_event(egui::Key::Backspace));
+                        }
+                        if ui
+                            .add_sized([44.0, 36.0], egui::Button::new("\u{23ce}"))
+                            .clicked()
+                        {
+                            self.pending_synthetic_events
+                                .push(synthetic_key_event(egui::Key::Enter));
+                        }
+                        ui.end_row();
+                    });
+            }
+
+            if self.timer.direction() == Direction::CountUp {
+                ui.add_space(8.0);
+                ui.horizontal(|ui| {
+                    if ui.button("Lap").clicked() {
+                        self.timer.record_lap();
+                    }
+                    ui.label(format!("{} laps", self.timer.laps().len()));
+                });
+                ui.add_space(4.0);
+                egui::ScrollArea::vertical()
+                    .max_height(80.0)
+                    .show(ui, |ui| {
+                        for (i, lap) in self.timer.laps().iter().enumerate() {
+                            ui.label(format!("#{:02}  {}", i + 1, fmt_hhmmss(lap.as_secs())));
+                        }
+                    });
+            }
+
             ui.add_space(10.0);
             ui.separator();
             ui.add_space(6.0);
@@ -205,16 +641,35 @@ impl eframe::App for AppState {
 
         // Finished modal
         if self.finished_modal {
+            // Single-shot countdowns (the default `cycles_total == 1`) behave
+            // like the plain baseline timer; Pomodoro-specific wording and
+            // controls only show up once the user has actually configured
+            // more than one cycle.
+            let is_pomodoro_work = self.phase == Phase::Work && self.cycles_total > 1;
             egui::Window::new("Time's up")
                 .collapsible(false)
                 .resizable(false)
                 .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
                 .show(ctx, |ui| {
-                    ui.label(egui::RichText::new("Timer finished.").size(18.0).strong());
+                    let message = if is_pomodoro_work {
+                        "Work phase finished."
+                    } else {
+                        "Timer finished."
+                    };
+                    ui.label(egui::RichText::new(message).size(18.0).strong());
                     ui.add_space(10.0);
                     ui.horizontal(|ui| {
+                        if is_pomodoro_work {
+                            if ui.button("Postpone").clicked() {
+                                self.postpone();
+                            }
+                            if ui.button("Start Rest").clicked() {
+                                self.start_rest();
+                            }
+                        }
                         if ui.button("OK").clicked() {
                             self.finished_modal = false;
+                            self.stop_alert();
                         }
                         ui.label(egui::RichText::new("(Esc closes)").weak());
                     });
@@ -223,6 +678,23 @@ impl eframe::App for AppState {
             // keep repainting while modal is visible
             ctx.request_repaint_after(Duration::from_millis(16));
         }
+
+        // Chime modal (half-hour/hourly clock chime, independent of the countdown)
+        if self.chime_modal {
+            egui::Window::new("Chime")
+                .collapsible(false)
+                .resizable(false)
+                .anchor(egui::Align2::CENTER_CENTER, [0.0, 40.0])
+                .show(ctx, |ui| {
+                    ui.label(egui::RichText::new("Chime!").size(18.0).strong());
+                    ui.add_space(10.0);
+                    if ui.button("OK").clicked() {
+                        self.chime_modal = false;
+                    }
+                });
+
+            ctx.request_repaint_after(Duration::from_millis(16));
+        }
     }
 }
 
@@ -238,8 +710,13 @@ fn main() -> eframe::Result<()> {
         "LightTimer",
         native_options,
         Box::new(|cc| {
-            cc.egui_ctx.set_visuals(egui::Visuals::light());
-            Box::<AppState>::default()
+            let config_path = config::default_config_path();
+            let config = Config::load_or_create(&config_path).unwrap_or_default();
+            cc.egui_ctx.set_visuals(match config.theme {
+                Theme::Light => egui::Visuals::light(),
+                Theme::Dark => egui::Visuals::dark(),
+            });
+            Box::new(AppState::new(config, config_path))
         }),
     )
 }
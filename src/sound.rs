@@ -0,0 +1,59 @@
+use rodio::source::{SineWave, Source};
+use rodio::{Decoder, OutputStream, OutputStreamHandle, Sink};
+use std::fs::File;
+use std::io::BufReader;
+use std::path::PathBuf;
+use std::time::Duration;
+
+/// How many times the finish alert repeats so it's noticeable when the window
+/// is backgrounded.
+const ALERT_REPEATS: u32 = 3;
+
+/// Owns the rodio output stream and plays the finish alert, decoding
+/// `sound_file` if present or falling back to a generated beep.
+pub struct AlertPlayer {
+    _stream: OutputStream,
+    handle: OutputStreamHandle,
+    sound_file: PathBuf,
+}
+
+impl AlertPlayer {
+    /// Opens the default audio output device. Returns `None` if no device is
+    /// available, in which case sound is silently skipped.
+    pub fn new(sound_file: PathBuf) -> Option<Self> {
+        let (stream, handle) = OutputStream::try_default().ok()?;
+        Some(Self {
+            _stream: stream,
+            handle,
+            sound_file,
+        })
+    }
+
+    /// Plays the alert at `volume` (0.0-1.0), repeating it a few times.
+    /// Returns the `Sink` so the caller can stop it early (e.g. when the
+    /// finished modal is dismissed).
+    pub fn play(&self, volume: f32) -> Option<Sink> {
+        let sink = Sink::try_new(&self.handle).ok()?;
+        sink.set_volume(volume);
+        for _ in 0..ALERT_REPEATS {
+            match self.decode_file() {
+                Some(source) => sink.append(source),
+                None => sink.append(beep()),
+            }
+        }
+        Some(sink)
+    }
+
+    fn decode_file(&self) -> Option<Decoder<BufReader<File>>> {
+        let file = File::open(&self.sound_file).ok()?;
+        Decoder::new(BufReader::new(file)).ok()
+    }
+}
+
+/// A short generated sine-wave beep used when the sound file is missing or
+/// cannot be decoded.
+fn beep() -> impl Source<Item = f32> + Send {
+    SineWave::new(880.0)
+        .take_duration(Duration::from_millis(200))
+        .amplify(0.5)
+}
@@ -0,0 +1,208 @@
+use std::time::Duration;
+
+/// Lifecycle of a [`TimerCore`]. `Finished` is only ever entered in
+/// [`Direction::CountDown`] mode; a stopwatch counts up indefinitely.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum PlayState {
+    Paused,
+    Running,
+    Finished,
+}
+
+/// Whether a [`TimerCore`] counts down toward zero or up from zero.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Direction {
+    CountDown,
+    CountUp,
+}
+
+/// Start/pause/reset/tick state machine, independent of any UI or wall clock.
+/// Callers compute `dt` from an `Instant` themselves and feed it to
+/// [`TimerCore::advance`], which keeps this type unit-testable without a real
+/// clock.
+pub struct TimerCore {
+    direction: Direction,
+    play_state: PlayState,
+    duration: Duration,
+    elapsed: Duration,
+    laps: Vec<Duration>,
+}
+
+impl TimerCore {
+    pub fn new(direction: Direction, duration: Duration) -> Self {
+        Self {
+            direction,
+            play_state: PlayState::Paused,
+            duration,
+            elapsed: Duration::ZERO,
+            laps: Vec::new(),
+        }
+    }
+
+    pub fn play_state(&self) -> PlayState {
+        self.play_state
+    }
+
+    pub fn direction(&self) -> Direction {
+        self.direction
+    }
+
+    /// Switches mode and resets the run (elapsed time and laps are mode-specific).
+    pub fn set_direction(&mut self, direction: Direction) {
+        self.direction = direction;
+        self.reset();
+    }
+
+    /// Sets the count-down target, resetting the run. No-op effect on
+    /// [`Direction::CountUp`] beyond the reset, since count-up has no target.
+    pub fn set_duration(&mut self, duration: Duration) {
+        self.duration = duration;
+        self.reset();
+    }
+
+    pub fn play(&mut self) {
+        if self.play_state != PlayState::Finished {
+            self.play_state = PlayState::Running;
+        }
+    }
+
+    pub fn pause(&mut self) {
+        if self.play_state == PlayState::Running {
+            self.play_state = PlayState::Paused;
+        }
+    }
+
+    pub fn toggle(&mut self) {
+        match self.play_state {
+            PlayState::Running => self.pause(),
+            PlayState::Paused | PlayState::Finished => self.play_state = PlayState::Running,
+        }
+    }
+
+    /// Clears elapsed time and laps and returns to `Paused`.
+    pub fn reset(&mut self) {
+        self.play_state = PlayState::Paused;
+        self.elapsed = Duration::ZERO;
+        self.laps.clear();
+    }
+
+    /// Advances the clock by `dt`. No-op unless `Running`.
+    pub fn advance(&mut self, dt: Duration) {
+        if self.play_state != PlayState::Running {
+            return;
+        }
+
+        self.elapsed += dt;
+        if self.direction == Direction::CountDown && self.elapsed >= self.duration {
+            self.elapsed = self.duration;
+            self.play_state = PlayState::Finished;
+        }
+    }
+
+    pub fn elapsed(&self) -> Duration {
+        self.elapsed
+    }
+
+    /// Time left until zero. Always `Duration::ZERO` in count-up mode.
+    pub fn remaining(&self) -> Duration {
+        if self.direction == Direction::CountUp {
+            return Duration::ZERO;
+        }
+        self.duration.saturating_sub(self.elapsed)
+    }
+
+    /// Records a split at the current elapsed time. Only meaningful while
+    /// counting up; ignored in count-down mode.
+    pub fn record_lap(&mut self) {
+        if self.direction == Direction::CountUp {
+            self.laps.push(self.elapsed);
+        }
+    }
+
+    pub fn laps(&self) -> &[Duration] {
+        &self.laps
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn countdown_advances_and_finishes_at_zero() {
+        let mut timer = TimerCore::new(Direction::CountDown, Duration::from_secs(10));
+        timer.play();
+
+        timer.advance(Duration::from_secs(4));
+        assert_eq!(timer.play_state(), PlayState::Running);
+        assert_eq!(timer.elapsed(), Duration::from_secs(4));
+        assert_eq!(timer.remaining(), Duration::from_secs(6));
+
+        timer.advance(Duration::from_secs(6));
+        assert_eq!(timer.play_state(), PlayState::Finished);
+        assert_eq!(timer.remaining(), Duration::ZERO);
+    }
+
+    #[test]
+    fn countdown_overshoot_clamps_remaining_to_zero() {
+        let mut timer = TimerCore::new(Direction::CountDown, Duration::from_secs(10));
+        timer.play();
+
+        timer.advance(Duration::from_secs(100));
+        assert_eq!(timer.play_state(), PlayState::Finished);
+        assert_eq!(timer.elapsed(), Duration::from_secs(10));
+        assert_eq!(timer.remaining(), Duration::ZERO);
+    }
+
+    #[test]
+    fn countup_never_finishes_and_has_no_upper_bound() {
+        let mut timer = TimerCore::new(Direction::CountUp, Duration::from_secs(10));
+        timer.play();
+
+        timer.advance(Duration::from_secs(100));
+        assert_eq!(timer.play_state(), PlayState::Running);
+        assert_eq!(timer.elapsed(), Duration::from_secs(100));
+        assert_eq!(timer.remaining(), Duration::ZERO);
+    }
+
+    #[test]
+    fn advance_is_a_no_op_when_not_running() {
+        let mut timer = TimerCore::new(Direction::CountDown, Duration::from_secs(10));
+        timer.advance(Duration::from_secs(5));
+        assert_eq!(timer.elapsed(), Duration::ZERO);
+        assert_eq!(timer.play_state(), PlayState::Paused);
+    }
+
+    #[test]
+    fn record_lap_only_applies_in_countup_mode() {
+        let mut stopwatch = TimerCore::new(Direction::CountUp, Duration::ZERO);
+        stopwatch.play();
+        stopwatch.advance(Duration::from_secs(3));
+        stopwatch.record_lap();
+        stopwatch.advance(Duration::from_secs(2));
+        stopwatch.record_lap();
+        assert_eq!(
+            stopwatch.laps(),
+            &[Duration::from_secs(3), Duration::from_secs(5)]
+        );
+
+        let mut countdown = TimerCore::new(Direction::CountDown, Duration::from_secs(10));
+        countdown.play();
+        countdown.advance(Duration::from_secs(2));
+        countdown.record_lap();
+        assert!(countdown.laps().is_empty());
+    }
+
+    #[test]
+    fn reset_clears_elapsed_and_laps_and_pauses() {
+        let mut timer = TimerCore::new(Direction::CountUp, Duration::ZERO);
+        timer.play();
+        timer.advance(Duration::from_secs(3));
+        timer.record_lap();
+
+        timer.reset();
+        assert_eq!(timer.play_state(), PlayState::Paused);
+        assert_eq!(timer.elapsed(), Duration::ZERO);
+        assert!(timer.laps().is_empty());
+    }
+}